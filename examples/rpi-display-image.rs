@@ -1,3 +1,4 @@
+use embedded_hal_bus::spi::ExclusiveDevice;
 use rppal::gpio;
 use rppal::hal;
 use rppal::spi;
@@ -8,23 +9,29 @@ enum Error {
     #[error("unknown")]
     Unknown,
     #[error("SPI")]
-    Spi(#[from] rppal::spi::Error),
+    Spi(#[from] ExclusiveDeviceError),
 }
 
+type ExclusiveDeviceError =
+    embedded_hal_bus::spi::DeviceError<rppal::spi::Error, std::convert::Infallible>;
+
 fn main() -> anyhow::Result<()> {
-    let spi = spi::Spi::new(
+    // `rppal::spi::Spi` already drives chip-select in hardware, so the
+    // embedded-hal 1.0 `SpiDevice` we hand to `uc8159` just needs a no-op CS.
+    let spi_bus = spi::Spi::new(
         spi::Bus::Spi0,
         spi::SlaveSelect::Ss0,
         3_000_000,
         spi::Mode::Mode0,
     )?;
+    let spi = ExclusiveDevice::new_no_delay(spi_bus, NoChipSelect)?;
     let gpio = gpio::Gpio::new()?;
     let delay = hal::Delay::new();
     let reset = gpio.get(27)?.into_output();
     let busy = gpio.get(17)?.into_input();
     let dc = gpio.get(22)?.into_output();
 
-    let mut display = uc8159::Display::<_, _, _, _, _, Error>::new(
+    let mut display = uc8159::InkyImpression::<_, _, _, _, _, Error>::new(
         spi,
         delay,
         reset,
@@ -65,8 +72,24 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
-impl From<()> for Error {
-    fn from(_: ()) -> Self {
-        Error::Unknown
+impl From<std::convert::Infallible> for Error {
+    fn from(infallible: std::convert::Infallible) -> Self {
+        match infallible {}
+    }
+}
+
+struct NoChipSelect;
+
+impl embedded_hal::digital::ErrorType for NoChipSelect {
+    type Error = std::convert::Infallible;
+}
+
+impl embedded_hal::digital::OutputPin for NoChipSelect {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
     }
 }
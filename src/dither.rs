@@ -0,0 +1,170 @@
+use crate::{Color, Display, Palette};
+
+/// Dithering algorithm used by [`Palette::dither_into`].
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Dither {
+    /// Floyd-Steinberg error diffusion. Produces the least banding but the
+    /// output differs pixel-to-pixel depending on everything upstream of it.
+    FloydSteinberg,
+    /// 4x4 Bayer ordered dithering. Deterministic and tileable, at the cost
+    /// of a visible dot pattern instead of diffused noise.
+    Ordered,
+}
+
+// 4x4 Bayer matrix, scaled to roughly +/-32 levels so it nudges pixels across
+// palette boundaries without overwhelming the underlying color.
+const BAYER_4X4: [[i16; 4]; 4] = [
+    [-32, 0, -24, 8],
+    [16, -16, 24, -8],
+    [-20, 12, -28, 4],
+    [28, -4, 20, -12],
+];
+
+impl Palette {
+    /// Quantizes `rgb` (tightly packed, row-major `[r, g, b, r, g, b, ...]`,
+    /// `width * height * 3` bytes) onto `out` using the given dithering
+    /// algorithm, instead of the independent per-pixel rounding that
+    /// [`Palette::closest_color`] performs.
+    ///
+    /// `width` and `height` must not exceed the dimensions of `out`; a smaller
+    /// image is written into the top-left corner and the rest of `out` is left
+    /// as it was.
+    pub fn dither_into<SPI, TIMER, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR>(
+        &self,
+        rgb: &[u8],
+        width: usize,
+        height: usize,
+        dither: Dither,
+        out: &mut Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>,
+    ) where
+        SPI: embedded_hal::spi::SpiDevice,
+        TIMER: embedded_hal::delay::DelayNs,
+        RESET: embedded_hal::digital::OutputPin,
+        BUSY: embedded_hal::digital::InputPin,
+        DC: embedded_hal::digital::OutputPin,
+        ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+    {
+        debug_assert_eq!(rgb.len(), width * height * 3);
+        debug_assert!(width <= WIDTH && height <= HEIGHT);
+
+        match dither {
+            Dither::FloydSteinberg => self.dither_floyd_steinberg(rgb, width, height, out),
+            Dither::Ordered => self.dither_ordered(rgb, width, height, out),
+        }
+    }
+
+    fn dither_floyd_steinberg<
+        SPI,
+        TIMER,
+        RESET,
+        BUSY,
+        DC,
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        ERR,
+    >(
+        &self,
+        rgb: &[u8],
+        width: usize,
+        height: usize,
+        out: &mut Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>,
+    ) where
+        SPI: embedded_hal::spi::SpiDevice,
+        TIMER: embedded_hal::delay::DelayNs,
+        RESET: embedded_hal::digital::OutputPin,
+        BUSY: embedded_hal::digital::InputPin,
+        DC: embedded_hal::digital::OutputPin,
+        ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+    {
+        let mut buf: alloc::vec::Vec<[i16; 3]> = alloc::vec![[0i16; 3]; width * height];
+        for (px, chunk) in buf.iter_mut().zip(rgb.chunks_exact(3)) {
+            *px = [chunk[0] as i16, chunk[1] as i16, chunk[2] as i16];
+        }
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = y * width + x;
+                let [r, g, b] = buf[idx];
+                let palette_idx = self.closest_index(clamp_u8(r), clamp_u8(g), clamp_u8(b));
+                let color = Color::all_significant()[palette_idx];
+                let [pr, pg, pb] = self.rgb(palette_idx);
+                out.set_pixel(x, y, color);
+
+                let err = [r - pr as i16, g - pg as i16, b - pb as i16];
+                // Weights: 7/16 right, 3/16 below-left, 5/16 below, 1/16 below-right.
+                spread_error(&mut buf, width, height, x as isize + 1, y as isize, err, 7);
+                spread_error(
+                    &mut buf,
+                    width,
+                    height,
+                    x as isize - 1,
+                    y as isize + 1,
+                    err,
+                    3,
+                );
+                spread_error(&mut buf, width, height, x as isize, y as isize + 1, err, 5);
+                spread_error(
+                    &mut buf,
+                    width,
+                    height,
+                    x as isize + 1,
+                    y as isize + 1,
+                    err,
+                    1,
+                );
+            }
+        }
+    }
+
+    fn dither_ordered<SPI, TIMER, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR>(
+        &self,
+        rgb: &[u8],
+        width: usize,
+        height: usize,
+        out: &mut Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>,
+    ) where
+        SPI: embedded_hal::spi::SpiDevice,
+        TIMER: embedded_hal::delay::DelayNs,
+        RESET: embedded_hal::digital::OutputPin,
+        BUSY: embedded_hal::digital::InputPin,
+        DC: embedded_hal::digital::OutputPin,
+        ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+    {
+        for y in 0..height {
+            for x in 0..width {
+                let idx = (y * width + x) * 3;
+                let bias = BAYER_4X4[y % 4][x % 4];
+                let r = clamp_u8(rgb[idx] as i16 + bias);
+                let g = clamp_u8(rgb[idx + 1] as i16 + bias);
+                let b = clamp_u8(rgb[idx + 2] as i16 + bias);
+                out.set_pixel(x, y, self.closest_color(r, g, b));
+            }
+        }
+    }
+}
+
+fn spread_error(
+    buf: &mut [[i16; 3]],
+    width: usize,
+    height: usize,
+    x: isize,
+    y: isize,
+    err: [i16; 3],
+    weight: i16,
+) {
+    if x < 0 || y < 0 || x as usize >= width || y as usize >= height {
+        return;
+    }
+    let idx = y as usize * width + x as usize;
+    for channel in 0..3 {
+        buf[idx][channel] = clamp_i16(buf[idx][channel] + err[channel] * weight / 16);
+    }
+}
+
+fn clamp_i16(value: i16) -> i16 {
+    value.clamp(0, 255)
+}
+
+fn clamp_u8(value: i16) -> u8 {
+    clamp_i16(value) as u8
+}
@@ -0,0 +1,82 @@
+use core::convert;
+
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Point, Size};
+use embedded_graphics_core::pixelcolor::PixelColor;
+use embedded_graphics_core::prelude::PointsIter;
+use embedded_graphics_core::primitives::Rectangle;
+use embedded_graphics_core::Pixel;
+
+use crate::{Color, Display};
+
+impl PixelColor for Color {
+    type Raw = ();
+}
+
+impl<SPI, TIMER, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR> OriginDimensions
+    for Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>
+where
+    SPI: embedded_hal::spi::SpiDevice,
+    TIMER: embedded_hal::delay::DelayNs,
+    RESET: embedded_hal::digital::OutputPin,
+    BUSY: embedded_hal::digital::InputPin,
+    DC: embedded_hal::digital::OutputPin,
+    ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+{
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
+
+impl<SPI, TIMER, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR> DrawTarget
+    for Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>
+where
+    SPI: embedded_hal::spi::SpiDevice,
+    TIMER: embedded_hal::delay::DelayNs,
+    RESET: embedded_hal::digital::OutputPin,
+    BUSY: embedded_hal::digital::InputPin,
+    DC: embedded_hal::digital::OutputPin,
+    ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+{
+    type Color = Color;
+    type Error = convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0
+                && point.y >= 0
+                && (point.x as usize) < WIDTH
+                && (point.y as usize) < HEIGHT
+            {
+                self.set_pixel(point.x as usize, point.y as usize, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if area.top_left == Point::zero() && area.size == self.size() {
+            self.fill(color);
+            return Ok(());
+        }
+
+        for point in area.points() {
+            if point.x >= 0
+                && point.y >= 0
+                && (point.x as usize) < WIDTH
+                && (point.y as usize) < HEIGHT
+            {
+                self.set_pixel(point.x as usize, point.y as usize, color);
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill(color);
+        Ok(())
+    }
+}
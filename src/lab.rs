@@ -0,0 +1,81 @@
+//! sRGB -> CIE L*a*b* conversion used by [`crate::Palette::closest_color_perceptual`].
+
+// D65 sRGB -> XYZ matrix (IEC 61966-2-1).
+const XYZ_MATRIX: [[f32; 3]; 3] = [
+    [0.4124564, 0.3575761, 0.1804375],
+    [0.2126729, 0.7151522, 0.072175],
+    [0.0193339, 0.119192, 0.9503041],
+];
+
+// CIE standard illuminant D65, 2-degree observer.
+const WHITE_POINT: [f32; 3] = [0.95047, 1.0, 1.08883];
+
+pub(crate) fn srgb_to_lab(r: u8, g: u8, b: u8) -> [f32; 3] {
+    let linear = [
+        srgb_to_linear(r as f32 / 255.0),
+        srgb_to_linear(g as f32 / 255.0),
+        srgb_to_linear(b as f32 / 255.0),
+    ];
+
+    let mut xyz = [0.0; 3];
+    for (row, out) in XYZ_MATRIX.iter().zip(xyz.iter_mut()) {
+        *out = row[0] * linear[0] + row[1] * linear[1] + row[2] * linear[2];
+    }
+
+    let [x, y, z] = [
+        f(xyz[0] / WHITE_POINT[0]),
+        f(xyz[1] / WHITE_POINT[1]),
+        f(xyz[2] / WHITE_POINT[2]),
+    ];
+
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+// f(t) from the XYZ -> Lab definition: a cube root above epsilon, a linear
+// ramp below it to avoid an infinite slope at t = 0.
+fn f(t: f32) -> f32 {
+    const EPSILON: f32 = 216.0 / 24389.0;
+    const KAPPA: f32 = 24389.0 / 27.0;
+    if t > EPSILON {
+        cbrt(t)
+    } else {
+        (KAPPA * t + 16.0) / 116.0
+    }
+}
+
+/// Squared CIE76 Euclidean distance between two Lab colors. Squared so
+/// callers comparing many candidates can skip the square root.
+pub(crate) fn delta_e76_squared(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let dl = a[0] - b[0];
+    let da = a[1] - b[1];
+    let db = a[2] - b[2];
+    dl * dl + da * da + db * db
+}
+
+#[cfg(feature = "std")]
+fn powf(base: f32, exp: f32) -> f32 {
+    base.powf(exp)
+}
+
+#[cfg(not(feature = "std"))]
+fn powf(base: f32, exp: f32) -> f32 {
+    libm::powf(base, exp)
+}
+
+#[cfg(feature = "std")]
+fn cbrt(value: f32) -> f32 {
+    value.cbrt()
+}
+
+#[cfg(not(feature = "std"))]
+fn cbrt(value: f32) -> f32 {
+    libm::cbrtf(value)
+}
@@ -1,8 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::convert;
 use core::marker;
-use core::mem;
-use core::slice;
+
+#[cfg(feature = "alloc")]
+mod dither;
+#[cfg(feature = "graphics")]
+mod draw_target;
+#[cfg(feature = "alloc")]
+pub use dither::Dither;
+#[cfg(feature = "alloc")]
+mod lut;
+#[cfg(feature = "alloc")]
+pub use lut::Lut;
+mod lab;
 
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
@@ -18,19 +31,28 @@ pub enum Color {
 }
 
 #[derive(Clone, Debug)]
-pub struct Palette([[u8; 3]; 7]);
+pub struct Palette {
+    rgb: [[u8; 3]; 7],
+    // CIE L*a*b* coordinates of each `rgb` entry, precomputed once so
+    // `closest_color_perceptual` doesn't redo the sRGB -> Lab conversion
+    // for every pixel it's asked to match.
+    lab: [[f32; 3]; 7],
+}
 
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct Config {
     pub border_color: Color,
 }
 
-// Currently hard-coded behavior for Pimoroni Inky Impression
-const WIDTH: usize = 600;
-const HEIGHT: usize = 448;
-
 const SPI_CHUNK_SIZE: usize = 4096;
 
+// `Display::buffer` needs to be sized from the `WIDTH`/`HEIGHT` const
+// generics, which isn't expressible as an array length on stable Rust.
+// Instead it's allocated at the largest size any supported panel needs
+// (the 600x448 Inky Impression) and only the `WIDTH / 2 * HEIGHT` prefix
+// that a given `Display` actually uses is read or written.
+const MAX_BUFFER_LEN: usize = 600 / 2 * 448;
+
 #[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
 enum Command {
@@ -64,14 +86,29 @@ enum Command {
     // TSSET = 0xE5,
 }
 
+/// A [`Display`] sized for the Pimoroni Inky Impression (the panel this
+/// crate originally targeted).
+pub type InkyImpression<SPI, TIMER, RESET, BUSY, DC, ERR = convert::Infallible> =
+    Display<SPI, TIMER, RESET, BUSY, DC, 600, 448, ERR>;
+
+// `SPI`/`TIMER`/`RESET`/`BUSY`/`DC` are embedded-hal 1.0 peripherals. Drivers
+// still stuck on embedded-hal 0.2 can bridge them with `embedded-hal-compat`.
 #[derive(Debug)]
-pub struct Display<SPI, TIMER, RESET, BUSY, DC, ERR = convert::Infallible>
-where
-    SPI: embedded_hal::blocking::spi::Write<u8>,
-    TIMER: embedded_hal::blocking::delay::DelayMs<u16>,
-    RESET: embedded_hal::digital::v2::OutputPin,
-    BUSY: embedded_hal::digital::v2::InputPin,
-    DC: embedded_hal::digital::v2::OutputPin,
+pub struct Display<
+    SPI,
+    TIMER,
+    RESET,
+    BUSY,
+    DC,
+    const WIDTH: usize,
+    const HEIGHT: usize,
+    ERR = convert::Infallible,
+> where
+    SPI: embedded_hal::spi::SpiDevice,
+    TIMER: embedded_hal::delay::DelayNs,
+    RESET: embedded_hal::digital::OutputPin,
+    BUSY: embedded_hal::digital::InputPin,
+    DC: embedded_hal::digital::OutputPin,
     ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
 {
     spi: SPI,
@@ -80,22 +117,28 @@ where
     busy: BUSY,
     dc: DC,
     config: Config,
-    buffer: [u8; WIDTH / 2 * HEIGHT],
+    buffer: [u8; MAX_BUFFER_LEN],
     phantom: marker::PhantomData<ERR>,
 }
 
-impl<SPI, DELAY, RESET, BUSY, DC, ERR> Display<SPI, DELAY, RESET, BUSY, DC, ERR>
+impl<SPI, DELAY, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR>
+    Display<SPI, DELAY, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>
 where
-    SPI: embedded_hal::blocking::spi::Write<u8>,
-    DELAY: embedded_hal::blocking::delay::DelayMs<u16>,
-    RESET: embedded_hal::digital::v2::OutputPin,
-    BUSY: embedded_hal::digital::v2::InputPin,
-    DC: embedded_hal::digital::v2::OutputPin,
+    SPI: embedded_hal::spi::SpiDevice,
+    DELAY: embedded_hal::delay::DelayNs,
+    RESET: embedded_hal::digital::OutputPin,
+    BUSY: embedded_hal::digital::InputPin,
+    DC: embedded_hal::digital::OutputPin,
     ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
 {
     pub fn new(spi: SPI, delay: DELAY, reset: RESET, busy: BUSY, dc: DC, config: Config) -> Self {
+        assert!(
+            Self::buffer_len() <= MAX_BUFFER_LEN,
+            "WIDTH x HEIGHT exceeds the largest panel this crate supports"
+        );
+
         let phantom = marker::PhantomData;
-        let buffer = [0; WIDTH / 2 * HEIGHT];
+        let buffer = [0; MAX_BUFFER_LEN];
 
         Self {
             spi,
@@ -109,6 +152,10 @@ where
         }
     }
 
+    fn buffer_len() -> usize {
+        WIDTH / 2 * HEIGHT
+    }
+
     pub fn width(&self) -> usize {
         WIDTH
     }
@@ -118,7 +165,7 @@ where
     }
 
     pub fn fill(&mut self, color: Color) {
-        self.buffer = [((color as u8) << 4) | color as u8; WIDTH / 2 * HEIGHT];
+        self.buffer[..Self::buffer_len()].fill(((color as u8) << 4) | color as u8);
     }
 
     pub fn copy_from(&mut self, color: &[Color]) {
@@ -139,9 +186,7 @@ where
     pub fn show(&mut self) -> Result<(), ERR> {
         self.setup()?;
 
-        let ptr = &self.buffer as *const _ as *const u8;
-        let len = mem::size_of_val(&self.buffer);
-        let data = unsafe { slice::from_raw_parts(ptr, len) };
+        let data = &self.buffer[..Self::buffer_len()];
 
         Self::send_command(&mut self.spi, &mut self.dc, Command::DTM1, data)?;
         self.busy_wait()?;
@@ -180,7 +225,7 @@ where
         )?;
 
         // Panel Setting
-        // 0b11000000 = Resolution select, 0b00 = 640x480, our panel is 0b11 = 600x448
+        // 0b11000000 = Resolution select, see `Self::resolution_select_bits`
         // 0b00100000 = LUT selection, 0 = ext flash, 1 = registers, we use ext flash
         // 0b00010000 = Ignore
         // 0b00001000 = Gate scan direction, 0 = down, 1 = up (default)
@@ -192,8 +237,8 @@ where
             &mut self.dc,
             Command::PSR,
             &[
-                0b11101111, // See above for more magic numbers
-                0x08,       // display_colours == UC8159_7C
+                (Self::resolution_select_bits() << 6) | 0b00101111, // See above for more magic numbers
+                0x08,                                               // display_colours == UC8159_7C
             ],
         )?;
 
@@ -257,6 +302,17 @@ where
         Ok(())
     }
 
+    // The UC8159 resolution-select bits (PSR bits 7:6) only recognize a
+    // handful of panel sizes, taken from the datasheet's resolution table.
+    fn resolution_select_bits() -> u8 {
+        match (WIDTH, HEIGHT) {
+            (600, 448) => 0b11,
+            (640, 400) => 0b10,
+            (400, 300) => 0b00,
+            _ => 0b11, // ??? - not documented in UC8159 datasheet, best guess
+        }
+    }
+
     fn busy_wait(&mut self) -> Result<(), ERR> {
         while self.busy.is_low()? {
             self.delay.delay_ms(10);
@@ -305,7 +361,7 @@ impl Color {
 
     pub fn palette(saturation: f32) -> Palette {
         let all_significant = Self::all_significant();
-        let mut colors = [[0; 3]; 7];
+        let mut rgb = [[0; 3]; 7];
         for (idx, color) in all_significant.iter().copied().enumerate() {
             let [rs, gs, bs] = color.as_rgb_saturated();
             let [rd, gd, bd] = color.as_rgb_desaturated();
@@ -313,9 +369,10 @@ impl Color {
             let g_corr = (gs as f32 * saturation + gd as f32 * (1.0 - saturation)) as u8;
             let b_corr = (bs as f32 * saturation + bd as f32 * (1.0 - saturation)) as u8;
 
-            colors[idx] = [r_corr, g_corr, b_corr];
+            rgb[idx] = [r_corr, g_corr, b_corr];
         }
-        Palette(colors)
+        let lab = rgb.map(|[r, g, b]| lab::srgb_to_lab(r, g, b));
+        Palette { rgb, lab }
     }
 
     fn as_rgb_desaturated(self) -> [u8; 3] {
@@ -346,9 +403,23 @@ impl Color {
 }
 
 impl Palette {
+    /// Finds the palette entry closest to `(r, g, b)` by plain squared RGB
+    /// distance. Fast, but doesn't match human color perception well - see
+    /// [`Palette::closest_color_perceptual`] for an alternative.
     pub fn closest_color(&self, r: u8, g: u8, b: u8) -> Color {
-        let idx = self
-            .0
+        Color::all_significant()[self.closest_index(r, g, b)]
+    }
+
+    /// Finds the palette entry closest to `(r, g, b)` by CIE76 ΔE in
+    /// L*a*b* space, which tracks perceived color difference much more
+    /// closely than Euclidean RGB distance, at the cost of converting the
+    /// incoming pixel to Lab on every call.
+    pub fn closest_color_perceptual(&self, r: u8, g: u8, b: u8) -> Color {
+        Color::all_significant()[self.closest_index_perceptual(r, g, b)]
+    }
+
+    fn closest_index(&self, r: u8, g: u8, b: u8) -> usize {
+        self.rgb
             .iter()
             .enumerate()
             .min_by_key(|(_, &[pr, pg, pb])| {
@@ -358,7 +429,26 @@ impl Palette {
                 dr * dr + dg * dg + db * db
             })
             .unwrap()
-            .0;
-        Color::all()[idx]
+            .0
+    }
+
+    fn closest_index_perceptual(&self, r: u8, g: u8, b: u8) -> usize {
+        let target = lab::srgb_to_lab(r, g, b);
+        self.lab
+            .iter()
+            .enumerate()
+            .min_by(|(_, &a), (_, &b)| {
+                lab::delta_e76_squared(a, target)
+                    .partial_cmp(&lab::delta_e76_squared(b, target))
+                    .unwrap()
+            })
+            .unwrap()
+            .0
+    }
+
+    // Only `dither` currently reads the raw RGB table back out by index.
+    #[cfg(feature = "alloc")]
+    fn rgb(&self, idx: usize) -> [u8; 3] {
+        self.rgb[idx]
     }
 }
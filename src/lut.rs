@@ -0,0 +1,77 @@
+use alloc::boxed::Box;
+
+use crate::{Color, Display, Palette};
+
+// 5 bits per channel: enough to stay visually lossless after quantization
+// while keeping the cube small (32 * 32 * 32 = 32768 entries).
+const BITS_PER_CHANNEL: u32 = 5;
+const STEPS: usize = 1 << BITS_PER_CHANNEL;
+
+/// A precomputed RGB -> [`Color`] lookup cube built by [`Palette::build_lut`].
+///
+/// Remapping a whole image with [`Palette::closest_color`] repeats the same
+/// 7-way scan for every pixel, even though only as many distinct colors as
+/// fit in the cube actually occur. Building the cube once and then calling
+/// [`Lut::lookup`] (or [`Display::copy_from_rgb`]) turns that per-pixel cost
+/// into an array index.
+pub struct Lut(Box<[Color; STEPS * STEPS * STEPS]>);
+
+impl Palette {
+    /// Builds a [`Lut`] by running [`Palette::closest_color`] once per
+    /// quantized cell, rather than once per pixel.
+    pub fn build_lut(&self) -> Lut {
+        let mut cube = Box::new([Color::Black; STEPS * STEPS * STEPS]);
+        for r in 0..STEPS {
+            for g in 0..STEPS {
+                for b in 0..STEPS {
+                    let color = self.closest_color(dequantize(r), dequantize(g), dequantize(b));
+                    cube[cube_index(r, g, b)] = color;
+                }
+            }
+        }
+        Lut(cube)
+    }
+}
+
+impl Lut {
+    /// Looks up the palette color closest to `(r, g, b)`, to the precision
+    /// the cube was built with.
+    pub fn lookup(&self, r: u8, g: u8, b: u8) -> Color {
+        self.0[cube_index(quantize(r), quantize(g), quantize(b))]
+    }
+}
+
+impl<SPI, TIMER, RESET, BUSY, DC, const WIDTH: usize, const HEIGHT: usize, ERR>
+    Display<SPI, TIMER, RESET, BUSY, DC, WIDTH, HEIGHT, ERR>
+where
+    SPI: embedded_hal::spi::SpiDevice,
+    TIMER: embedded_hal::delay::DelayNs,
+    RESET: embedded_hal::digital::OutputPin,
+    BUSY: embedded_hal::digital::InputPin,
+    DC: embedded_hal::digital::OutputPin,
+    ERR: From<SPI::Error> + From<RESET::Error> + From<BUSY::Error> + From<DC::Error>,
+{
+    /// Fills the display buffer from a tightly packed, row-major
+    /// `[r, g, b, r, g, b, ...]` image of exactly `WIDTH * HEIGHT * 3`
+    /// bytes, using `lut` instead of [`Palette::closest_color`] per pixel.
+    pub fn copy_from_rgb(&mut self, rgb: &[u8], lut: &Lut) {
+        debug_assert_eq!(rgb.len(), WIDTH * HEIGHT * 3);
+        for (idx, chunk) in rgb.chunks_exact(3).enumerate() {
+            let color = lut.lookup(chunk[0], chunk[1], chunk[2]);
+            self.set_pixel(idx % WIDTH, idx / WIDTH, color);
+        }
+    }
+}
+
+fn quantize(channel: u8) -> usize {
+    (channel as usize) >> (8 - BITS_PER_CHANNEL)
+}
+
+fn dequantize(step: usize) -> u8 {
+    // Sample the center of the quantization bucket rather than its edge.
+    ((step << (8 - BITS_PER_CHANNEL)) + (1 << (8 - BITS_PER_CHANNEL - 1))) as u8
+}
+
+fn cube_index(r: usize, g: usize, b: usize) -> usize {
+    (r * STEPS + g) * STEPS + b
+}